@@ -0,0 +1,373 @@
+//! Stateful poll-filter subsystem for the `shadow` namespace, mirroring the semantics of
+//! `eth_newFilter` / `eth_getFilterChanges` / `eth_getFilterLogs` / `eth_uninstallFilter`.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use alloy_rpc_types::{Filter, FilterBlockOption};
+use dashmap::DashMap;
+use jsonrpsee::{
+    core::RpcResult,
+    types::{
+        error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+        ErrorObject,
+    },
+    Extensions,
+};
+use reth::providers::{BlockNumReader, BlockReaderIdExt};
+use reth_primitives::BlockNumberOrTag;
+
+use super::{
+    get_logs::{query_logs, GetLogsResult, ValidatedQueryParams},
+    rate_limit,
+};
+use crate::ShadowRpc;
+
+/// Opaque identifier for a live poll filter, returned by `shadow_newFilter`.
+pub(crate) type FilterId = String;
+
+/// Counter used to mint unique filter ids.
+static NEXT_FILTER_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A server-side filter tracking the cursor of a `shadow_getFilterChanges` poll.
+pub(crate) struct PollFilter {
+    /// The validated parameters the filter was created with.
+    params: ValidatedQueryParams,
+    /// Last block number reported to a `getFilterChanges` call, or `None` if the filter hasn't
+    /// been polled yet.
+    last_polled_block: Option<u64>,
+    /// Wall-clock time of the filter's last poll, used by the reaper to expire idle filters.
+    last_accessed: Instant,
+    /// Whether the filter's upper bound tracks the chain head rather than a fixed block, i.e. it
+    /// was created with an open `toBlock` (omitted or `"latest"`). `getFilterLogs` re-resolves
+    /// this to the current latest block on every call instead of reusing the block number that
+    /// was "latest" at filter-creation time.
+    tracks_latest: bool,
+}
+
+pub(crate) async fn new_filter<P>(rpc: &ShadowRpc<P>, filter: Filter) -> RpcResult<FilterId>
+where
+    P: BlockNumReader + BlockReaderIdExt + Clone + Unpin + 'static,
+{
+    let tracks_latest = matches!(
+        &filter.block_option,
+        FilterBlockOption::Range { to_block, .. }
+            if matches!(to_block, None | Some(BlockNumberOrTag::Latest))
+    );
+
+    let validated = ValidatedQueryParams::new(&rpc.provider, filter)?;
+    let id = NEXT_FILTER_ID.fetch_add(1, Ordering::Relaxed).to_string();
+    rpc.filters.insert(
+        id.clone(),
+        PollFilter {
+            params: validated,
+            last_polled_block: None,
+            last_accessed: Instant::now(),
+            tracks_latest,
+        },
+    );
+    Ok(id)
+}
+
+pub(crate) async fn get_filter_changes<P>(
+    rpc: &ShadowRpc<P>,
+    ext: &Extensions,
+    id: FilterId,
+) -> RpcResult<Vec<GetLogsResult>>
+where
+    P: BlockNumReader + BlockReaderIdExt + Clone + Unpin + 'static,
+{
+    let latest = latest_block(&rpc.provider)?;
+
+    let query = {
+        let mut entry = rpc.filters.get_mut(&id).ok_or_else(|| filter_not_found(&id))?;
+        entry.last_accessed = Instant::now();
+        let from_block = entry.last_polled_block.map_or(entry.params.from_block, |b| b + 1);
+        if from_block > latest {
+            return Ok(vec![]);
+        }
+        ValidatedQueryParams { from_block, to_block: latest, ..entry.params.clone() }
+    };
+
+    // Only commit the cursor once the query has actually succeeded: advancing it on a rejected
+    // or failed call would permanently drop the logs between the old cursor and `latest`.
+    rpc.rate_limiter.deduct(rate_limit::connection_id(ext), rate_limit::compute_cost(&query))?;
+    let logs = query_logs(&rpc.pool, &query).await?;
+
+    if let Some(mut entry) = rpc.filters.get_mut(&id) {
+        entry.last_polled_block = Some(latest);
+    }
+
+    Ok(logs)
+}
+
+pub(crate) async fn get_filter_logs<P>(
+    rpc: &ShadowRpc<P>,
+    ext: &Extensions,
+    id: FilterId,
+) -> RpcResult<Vec<GetLogsResult>>
+where
+    P: BlockNumReader + BlockReaderIdExt + Clone + Unpin + 'static,
+{
+    let (params, tracks_latest) = {
+        let mut entry = rpc.filters.get_mut(&id).ok_or_else(|| filter_not_found(&id))?;
+        entry.last_accessed = Instant::now();
+        (entry.params.clone(), entry.tracks_latest)
+    };
+
+    let params = if tracks_latest {
+        ValidatedQueryParams { to_block: latest_block(&rpc.provider)?, ..params }
+    } else {
+        params
+    };
+
+    rpc.rate_limiter.deduct(rate_limit::connection_id(ext), rate_limit::compute_cost(&params))?;
+    query_logs(&rpc.pool, &params).await
+}
+
+pub(crate) async fn uninstall_filter<P>(rpc: &ShadowRpc<P>, id: FilterId) -> RpcResult<bool>
+where
+    P: BlockNumReader + BlockReaderIdExt + Clone + Unpin + 'static,
+{
+    Ok(rpc.filters.remove(&id).is_some())
+}
+
+/// Periodically drops filters that haven't been polled within `ttl`, so the filter map doesn't
+/// grow unbounded when clients abandon filters without uninstalling them.
+pub(crate) async fn reap_idle_filters(
+    filters: Arc<DashMap<FilterId, PollFilter>>,
+    ttl: Duration,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        reap_expired(&filters, ttl);
+    }
+}
+
+fn reap_expired(filters: &DashMap<FilterId, PollFilter>, ttl: Duration) {
+    filters.retain(|_, filter| filter.last_accessed.elapsed() < ttl);
+}
+
+fn latest_block(provider: &(impl BlockNumReader + Clone)) -> RpcResult<u64> {
+    provider
+        .best_block_number()
+        .map_err(|e| ErrorObject::owned::<()>(INTERNAL_ERROR_CODE, e.to_string(), None))
+}
+
+fn filter_not_found(id: &str) -> ErrorObject<'static> {
+    ErrorObject::owned::<()>(INVALID_PARAMS_CODE, format!("filter not found: {id}"), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, Address};
+    use reth::providers::test_utils::MockEthProvider;
+    use reth_primitives::{Block, Header};
+    use sqlx::SqlitePool;
+
+    use super::*;
+    use crate::apis::rate_limit::{RateLimiter, DEFAULT_MAX_CREDITS, DEFAULT_RECHARGE_RATE};
+
+    fn block_at(number: u64) -> (alloy_primitives::B256, Block) {
+        let block = Block { header: Header { number, ..Default::default() }, ..Default::default() };
+        (block.hash_slow(), block)
+    }
+
+    async fn test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query(
+            r#"
+            CREATE TABLE shadow_logs (
+                address BLOB NOT NULL,
+                block_hash BLOB NOT NULL,
+                block_log_index INTEGER NOT NULL,
+                block_number INTEGER NOT NULL,
+                data BLOB NOT NULL,
+                removed BOOLEAN NOT NULL,
+                topic_0 BLOB,
+                topic_1 BLOB,
+                topic_2 BLOB,
+                topic_3 BLOB,
+                transaction_hash BLOB NOT NULL,
+                transaction_index INTEGER NOT NULL,
+                transaction_log_index INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    async fn insert_log(pool: &SqlitePool, address: Address, block_number: u64) {
+        sqlx::query(
+            "INSERT INTO shadow_logs \
+             (address, block_hash, block_log_index, block_number, data, removed, \
+              transaction_hash, transaction_index, transaction_log_index) \
+             VALUES (?, ?, 0, ?, ?, false, ?, 0, 0)",
+        )
+        .bind(address.as_slice().to_vec())
+        .bind(vec![0u8; 32])
+        .bind(block_number as i64)
+        .bind(Vec::<u8>::new())
+        .bind(vec![0u8; 32])
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    fn test_rpc(provider: MockEthProvider, pool: SqlitePool) -> ShadowRpc<MockEthProvider> {
+        ShadowRpc {
+            pool,
+            provider,
+            filters: Arc::new(DashMap::new()),
+            rate_limiter: Arc::new(RateLimiter::new(DEFAULT_MAX_CREDITS, DEFAULT_RECHARGE_RATE)),
+        }
+    }
+
+    #[tokio::test]
+    async fn filter_changes_advances_cursor_and_reports_only_new_logs() {
+        let address = address!("1234567890123456789012345678901234567890");
+        let provider = MockEthProvider::default();
+        provider.extend_blocks([block_at(0), block_at(5)]);
+
+        let pool = test_pool().await;
+        insert_log(&pool, address, 5).await;
+        let rpc = test_rpc(provider, pool);
+        let ext = Extensions::new();
+
+        let id = new_filter(&rpc, Filter::new().address(address)).await.unwrap();
+
+        let first = get_filter_changes(&rpc, &ext, id.clone()).await.unwrap();
+        assert_eq!(first.len(), 1, "first poll should see the log present at filter creation");
+
+        let second = get_filter_changes(&rpc, &ext, id.clone()).await.unwrap();
+        assert!(second.is_empty(), "cursor must advance so unchanged chain state yields no logs");
+
+        rpc.provider.extend_blocks([block_at(6)]);
+        insert_log(&rpc.pool, address, 6).await;
+
+        let third = get_filter_changes(&rpc, &ext, id).await.unwrap();
+        assert_eq!(third.len(), 1, "a newly indexed block must be picked up by the next poll");
+    }
+
+    #[tokio::test]
+    async fn filter_changes_does_not_skip_genesis_block() {
+        let address = address!("1234567890123456789012345678901234567890");
+        let provider = MockEthProvider::default();
+        provider.extend_blocks([block_at(0)]);
+
+        let pool = test_pool().await;
+        insert_log(&pool, address, 0).await;
+        let rpc = test_rpc(provider, pool);
+        let ext = Extensions::new();
+
+        let id =
+            new_filter(&rpc, Filter::new().address(address).from_block(0u64).to_block(0u64))
+                .await
+                .unwrap();
+
+        let logs = get_filter_changes(&rpc, &ext, id).await.unwrap();
+        assert_eq!(logs.len(), 1, "a filter starting at block 0 must not skip genesis");
+    }
+
+    #[tokio::test]
+    async fn filter_changes_does_not_advance_cursor_when_rejected() {
+        let address = address!("1234567890123456789012345678901234567890");
+        let provider = MockEthProvider::default();
+        provider.extend_blocks([block_at(0), block_at(5)]);
+
+        let pool = test_pool().await;
+        insert_log(&pool, address, 5).await;
+
+        let rpc = ShadowRpc {
+            pool,
+            provider,
+            filters: Arc::new(DashMap::new()),
+            // Zero credits: the deduct call is always rejected.
+            rate_limiter: Arc::new(RateLimiter::new(0, 0)),
+        };
+        let ext = Extensions::new();
+
+        let id = new_filter(&rpc, Filter::new().address(address)).await.unwrap();
+
+        assert!(
+            get_filter_changes(&rpc, &ext, id.clone()).await.is_err(),
+            "zero credits must reject the call"
+        );
+        assert_eq!(
+            rpc.filters.get(&id).unwrap().last_polled_block,
+            None,
+            "a rejected call must not consume the filter's cursor"
+        );
+    }
+
+    #[tokio::test]
+    async fn filter_logs_tracks_latest_for_open_ended_range() {
+        let address = address!("1234567890123456789012345678901234567890");
+        let provider = MockEthProvider::default();
+        provider.extend_blocks([block_at(0), block_at(5)]);
+
+        let pool = test_pool().await;
+        insert_log(&pool, address, 5).await;
+        let rpc = test_rpc(provider, pool);
+        let ext = Extensions::new();
+
+        let id = new_filter(&rpc, Filter::new().address(address)).await.unwrap();
+
+        let logs = get_filter_logs(&rpc, &ext, id.clone()).await.unwrap();
+        assert_eq!(logs.len(), 1);
+
+        rpc.provider.extend_blocks([block_at(6)]);
+        insert_log(&rpc.pool, address, 6).await;
+
+        let logs = get_filter_logs(&rpc, &ext, id).await.unwrap();
+        assert_eq!(
+            logs.len(),
+            2,
+            "getFilterLogs must reflect newly indexed blocks, not the latest block frozen at \
+             filter creation"
+        );
+    }
+
+    #[tokio::test]
+    async fn uninstall_filter_is_idempotent() {
+        let provider = MockEthProvider::default();
+        provider.extend_blocks([block_at(0)]);
+        let rpc = test_rpc(provider, test_pool().await);
+
+        let id = new_filter(&rpc, Filter::new()).await.unwrap();
+        assert!(uninstall_filter(&rpc, id.clone()).await.unwrap());
+        assert!(!uninstall_filter(&rpc, id).await.unwrap(), "second uninstall has nothing to remove");
+    }
+
+    #[tokio::test]
+    async fn reap_expired_drops_filters_past_ttl() {
+        let filters = Arc::new(DashMap::new());
+        filters.insert(
+            "stale".to_string(),
+            PollFilter {
+                params: ValidatedQueryParams {
+                    from_block: 0,
+                    to_block: 0,
+                    addresses: vec![],
+                    topics: [None, None, None, None],
+                },
+                last_polled_block: None,
+                last_accessed: Instant::now() - Duration::from_secs(120),
+                tracks_latest: false,
+            },
+        );
+
+        reap_expired(&filters, Duration::from_secs(60));
+        assert!(filters.is_empty());
+    }
+}
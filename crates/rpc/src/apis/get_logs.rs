@@ -1,14 +1,17 @@
-use std::str::FromStr;
-
+use alloy_primitives::{Address, Bytes, FixedBytes, B256, U256, U64};
+use alloy_rpc_types::{Filter, FilterBlockOption};
 use jsonrpsee::{
-    core::{async_trait, RpcResult},
+    core::RpcResult,
     types::{error::INTERNAL_ERROR_CODE, ErrorObject},
+    Extensions,
 };
 use reth::providers::{BlockNumReader, BlockReaderIdExt};
-use reth_primitives::{revm_primitives::FixedBytes, BlockNumberOrTag};
+use reth_primitives::BlockNumberOrTag;
 use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
 
-use crate::{ShadowRpc, ShadowRpcApiServer};
+use super::rate_limit;
+use crate::{error::ShadowRpcError, ShadowRpc};
 
 /// `shadow_getLogs` RPC request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,24 +22,8 @@ pub struct GetLogsRpcRequest {
     pub json_rpc: String,
     /// Indicates the method to be invoked.
     pub method: String,
-    /// Contains parameters for request.
-    pub params: Vec<GetLogsParameters>,
-}
-
-/// Unvalidated parameters for `shadow_getLogs` RPC requests.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct GetLogsParameters {
-    /// Contains contract addresses from which logs should originate.
-    pub address: Vec<String>,
-    /// Hash of block from which logs should originate. Using this field is equivalent
-    /// to passing identical values for `fromBlock` and `toBlock`.
-    pub block_hash: Option<String>,
-    /// Start of block range from which logs should originate.
-    pub from_block: Option<String>,
-    /// End of block range from which logs should originate.
-    pub to_block: Option<String>,
-    /// Array of 32-byte data topics.
-    pub topics: Vec<String>,
+    /// Contains parameters for request, using the same `Filter` shape as `eth_getLogs`.
+    pub params: Vec<Filter>,
 }
 
 /// `shadow_getLogs` RPC response.
@@ -50,28 +37,31 @@ pub struct GetLogsResponse {
     pub result: Vec<GetLogsResult>,
 }
 
-/// Inner result type for `shadow_getLogs` RPC responses.
+/// Inner result type for `shadow_getLogs` RPC responses, byte-for-byte compatible with what a
+/// client expects from `eth_getLogs`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct GetLogsResult {
     /// Contract address from which the log originated.
-    pub address: String,
+    pub address: Address,
     /// Hash of block from which the log originated.
-    pub block_hash: String,
+    pub block_hash: B256,
     /// Block number from which the log originated.
-    pub block_number: String,
+    pub block_number: U64,
     /// Contains one or more 32-byte non-indexed arguments of the log.
-    pub data: String,
+    pub data: Bytes,
     /// Integer of the log index in the containing block.
-    pub log_index: String,
+    pub log_index: U256,
     /// Indicates whether the log has been removed from the canonical chain.
     pub removed: bool,
-    /// Array of topics.
-    pub topics: [Option<String>; 4],
+    /// Topics, in the order they were emitted.
+    pub topics: Vec<B256>,
     /// Hash of transaction from which the log originated.
-    pub transaction_hash: String,
+    pub transaction_hash: B256,
     /// Integer of the transaction index position from which the log originated.
-    pub transaction_index: String,
+    pub transaction_index: U64,
 }
+
 /// Helper type for ease of use in converting rows from the `shadow_getLogs`
 /// query into the `GetLogsResult` type which is used in `GetLogsResponse`.
 #[derive(Debug, sqlx::FromRow)]
@@ -113,274 +103,215 @@ pub(crate) struct ValidatedQueryParams {
     /// End of block range from which logs will be filtered.
     pub(crate) to_block: u64,
     /// Set of addresses from which logs will be filtered.
-    pub(crate) addresses: Vec<String>,
-    /// Set of log topics.
-    pub(crate) topics: [Option<String>; 4],
+    pub(crate) addresses: Vec<Address>,
+    /// Set of log topics. Each position is either a wildcard or a set of alternatives matched
+    /// via OR.
+    pub(crate) topics: [Option<Vec<B256>>; 4],
 }
 
-impl From<RawGetLogsRow> for GetLogsResult {
-    fn from(value: RawGetLogsRow) -> Self {
-        Self {
-            address: format!("0x{}", hex::encode(value.address)),
-            block_hash: format!("0x{}", hex::encode(value.block_hash)),
-            block_number: hex::encode(value.block_number.to_be_bytes()),
-            data: format!("0x{}", hex::encode(value.data)),
-            log_index: value.block_log_index.to_string(),
+impl TryFrom<RawGetLogsRow> for GetLogsResult {
+    type Error = ShadowRpcError;
+
+    fn try_from(value: RawGetLogsRow) -> Result<Self, Self::Error> {
+        let topics = [value.topic_0, value.topic_1, value.topic_2, value.topic_3]
+            .into_iter()
+            .flatten()
+            .map(|topic| decode_fixed("topic", &topic))
+            .collect::<Result<Vec<B256>, ShadowRpcError>>()?;
+
+        Ok(Self {
+            address: Address::try_from(value.address.as_slice())
+                .map_err(|_| ShadowRpcError::MalformedRow {
+                    field: "address",
+                    len: value.address.len(),
+                })?,
+            block_hash: decode_fixed("block_hash", &value.block_hash)?,
+            block_number: U64::from(value.block_number as u64),
+            data: Bytes::from(value.data),
+            log_index: U256::from(value.block_log_index),
             removed: value.removed,
-            topics: [
-                value.topic_0.map(|t| format!("0x{}", hex::encode(t))),
-                value.topic_1.map(|t| format!("0x{}", hex::encode(t))),
-                value.topic_2.map(|t| format!("0x{}", hex::encode(t))),
-                value.topic_3.map(|t| format!("0x{}", hex::encode(t))),
-            ],
-            transaction_hash: format!("0x{}", hex::encode(value.transaction_hash)),
-            transaction_index: value.transaction_index.to_string(),
-        }
+            topics,
+            transaction_hash: decode_fixed("transaction_hash", &value.transaction_hash)?,
+            transaction_index: U64::from(value.transaction_index as u64),
+        })
     }
 }
 
-#[async_trait]
-impl<P> ShadowRpcApiServer for ShadowRpc<P>
+/// Decodes a fixed-width `shadow_logs` column (a block/transaction/topic hash) from its raw
+/// bytes, without panicking if a future schema change or a buggy indexer write stored the wrong
+/// length.
+fn decode_fixed<const N: usize>(
+    field: &'static str,
+    bytes: &[u8],
+) -> Result<FixedBytes<N>, ShadowRpcError> {
+    FixedBytes::try_from(bytes).map_err(|_| ShadowRpcError::MalformedRow { field, len: bytes.len() })
+}
+
+/// Implementation backing `ShadowRpcApiServer::get_logs`. The poll-filter subsystem in
+/// [`super::filter`] shares [`query_logs`] and [`ValidatedQueryParams`] with it.
+pub(crate) async fn get_logs<P>(
+    rpc: &ShadowRpc<P>,
+    ext: &Extensions,
+    req: GetLogsRpcRequest,
+) -> RpcResult<GetLogsResponse>
 where
     P: BlockNumReader + BlockReaderIdExt + Clone + Unpin + 'static,
 {
-    #[doc = "Returns shadow logs."]
-    async fn get_logs(&self, req: GetLogsRpcRequest) -> RpcResult<GetLogsResponse> {
-        let base_stmt = r#"
-            SELECT
-                address,
-                block_hash,
-                block_log_index,
-                block_number,
-                data,
-                removed,
-                topic_0,
-                topic_1,
-                topic_2,
-                topic_3,
-                transaction_hash,
-                transaction_index,
-                transaction_log_index
-            FROM shadow_logs
-        "#;
-
-        let validated_param_objs = req
-            .params
-            .into_iter()
-            .map(|param_obj| ValidatedQueryParams::new(&self.provider, param_obj))
-            .collect::<RpcResult<Vec<ValidatedQueryParams>>>()?;
-
-        let mut results: Vec<GetLogsResult> = vec![];
-        for query_params in validated_param_objs {
-            let sql = format!("{base_stmt} {query_params}");
-            let raw_rows: Vec<RawGetLogsRow> = sqlx::query_as(&sql)
-                .fetch_all(&self.pool)
-                .await
-                .map_err(|e| ErrorObject::owned::<()>(INTERNAL_ERROR_CODE, e.to_string(), None))?;
-            let mut result =
-                raw_rows.into_iter().map(GetLogsResult::from).collect::<Vec<GetLogsResult>>();
-            results.append(&mut result);
+    let validated_param_objs = req
+        .params
+        .into_iter()
+        .map(|filter| ValidatedQueryParams::new(&rpc.provider, filter))
+        .collect::<Result<Vec<ValidatedQueryParams>, ShadowRpcError>>()?;
+
+    let connection_id = rate_limit::connection_id(ext);
+
+    let mut results: Vec<GetLogsResult> = vec![];
+    for query_params in &validated_param_objs {
+        rpc.rate_limiter.deduct(connection_id, rate_limit::compute_cost(query_params))?;
+        results.append(&mut query_logs(&rpc.pool, query_params).await?);
+    }
+
+    Ok(GetLogsResponse { id: req.id, json_rpc: req.json_rpc, result: results })
+}
+
+const BASE_STMT: &str = r#"
+    SELECT
+        address,
+        block_hash,
+        block_log_index,
+        block_number,
+        data,
+        removed,
+        topic_0,
+        topic_1,
+        topic_2,
+        topic_3,
+        transaction_hash,
+        transaction_index,
+        transaction_log_index
+    FROM shadow_logs
+"#;
+
+/// Executes a single validated query against the `shadow_logs` table.
+pub(crate) async fn query_logs(
+    pool: &SqlitePool,
+    query_params: &ValidatedQueryParams,
+) -> RpcResult<Vec<GetLogsResult>> {
+    let raw_rows: Vec<RawGetLogsRow> = build_query(query_params)
+        .build_query_as()
+        .fetch_all(pool)
+        .await
+        .map_err(|e| ErrorObject::owned::<()>(INTERNAL_ERROR_CODE, e.to_string(), None))?;
+
+    raw_rows
+        .into_iter()
+        .map(|row| GetLogsResult::try_from(row).map_err(Into::into))
+        .collect()
+}
+
+/// Builds a parameterized `shadow_logs` query for `query_params`, binding addresses, the block
+/// range, and topic sets rather than interpolating them into the SQL text.
+fn build_query(query_params: &ValidatedQueryParams) -> QueryBuilder<'_, Sqlite> {
+    let mut builder = QueryBuilder::new(BASE_STMT);
+    let mut has_where = false;
+
+    if !query_params.addresses.is_empty() {
+        builder.push(" WHERE address IN (");
+        let mut separated = builder.separated(", ");
+        for address in &query_params.addresses {
+            separated.push_bind(address.as_slice().to_vec());
         }
+        builder.push(")");
+        has_where = true;
+    }
 
-        Ok(GetLogsResponse { id: req.id, json_rpc: req.json_rpc, result: results })
+    builder.push(if has_where { " AND " } else { " WHERE " });
+    builder.push("block_number BETWEEN ");
+    builder.push_bind(query_params.from_block as i64);
+    builder.push(" AND ");
+    builder.push_bind(query_params.to_block as i64);
+    has_where = true;
+
+    for (idx, topic_set) in query_params.topics.iter().enumerate() {
+        let Some(topics) = topic_set else { continue };
+        if topics.is_empty() {
+            continue;
+        }
+
+        builder.push(if has_where { " AND " } else { " WHERE " });
+        builder.push(format!("topic_{idx} IN ("));
+        let mut separated = builder.separated(", ");
+        for topic in topics {
+            separated.push_bind(topic.as_slice().to_vec());
+        }
+        builder.push(")");
+        has_where = true;
     }
+
+    builder
 }
 
 impl ValidatedQueryParams {
-    fn new(
+    pub(crate) fn new(
         provider: &(impl BlockNumReader + BlockReaderIdExt + Clone + Unpin + 'static),
-        params: GetLogsParameters,
-    ) -> RpcResult<Self> {
-        let (from_block, to_block) = match (params.block_hash, params.from_block, params.to_block) {
-            (None, None, None) => {
-                let num = match provider.block_by_number_or_tag(BlockNumberOrTag::Latest) {
-                    Ok(Some(b)) => b.number,
-                    Ok(None) => {
-                        return Err(ErrorObject::owned::<()>(
-                            -1,
-                            "No block found for block number or tag: latest",
-                            None,
-                        ))
-                    }
-                    Err(e) => return Err(ErrorObject::owned::<()>(-1, e.to_string(), None)),
-                };
+        filter: Filter,
+    ) -> Result<Self, ShadowRpcError> {
+        let (from_block, to_block) = match filter.block_option {
+            FilterBlockOption::AtBlockHash(hash) => {
+                let num = provider
+                    .block_by_hash(hash)?
+                    .ok_or_else(|| ShadowRpcError::BlockNotFound(format!("block hash: {hash}")))?
+                    .number;
                 (num, num)
             }
-            (None, None, Some(to_block)) => {
-                let from = match provider.block_by_number_or_tag(BlockNumberOrTag::Latest) {
-                    Ok(Some(b)) => b.number,
-                    Ok(None) => {
-                        return Err(ErrorObject::owned::<()>(
-                            -1,
-                            "No block found for block number or tag: latest",
-                            None,
-                        ))
-                    }
-                    Err(e) => return Err(ErrorObject::owned::<()>(-1, e.to_string(), None)),
-                };
-                let to_tag = BlockNumberOrTag::from_str(&to_block)
-                    .map_err(|e| ErrorObject::owned::<()>(-1, e.to_string(), None))?;
-                let to = match provider.block_by_number_or_tag(to_tag) {
-                    Ok(Some(b)) => b.number,
-                    Ok(None) => {
-                        return Err(ErrorObject::owned::<()>(
-                            -1,
-                            format!("No block found for block number or tag: {to_tag}"),
-                            None,
-                        ))
-                    }
-                    Err(e) => return Err(ErrorObject::owned::<()>(-1, e.to_string(), None)),
-                };
+            FilterBlockOption::Range { from_block, to_block } => {
+                let from = resolve_tag(provider, from_block.unwrap_or(BlockNumberOrTag::Latest))?;
+                let to = resolve_tag(provider, to_block.unwrap_or(BlockNumberOrTag::Latest))?;
                 (from, to)
             }
-            (None, Some(from_block), None) => {
-                let from_tag = BlockNumberOrTag::from_str(&from_block)
-                    .map_err(|e| ErrorObject::owned::<()>(-1, e.to_string(), None))?;
-                let from = match provider.block_by_number_or_tag(from_tag) {
-                    Ok(Some(b)) => b.number,
-                    Ok(None) => {
-                        return Err(ErrorObject::owned::<()>(
-                            -1,
-                            format!("No block found for block number or tag: {from_tag}"),
-                            None,
-                        ))
-                    }
-                    Err(e) => return Err(ErrorObject::owned::<()>(-1, e.to_string(), None)),
-                };
-                let to = match provider.block_by_number_or_tag(BlockNumberOrTag::Latest) {
-                    Ok(Some(b)) => b.number,
-                    Ok(None) => {
-                        return Err(ErrorObject::owned::<()>(
-                            -1,
-                            "No block found for block number or tag: latest",
-                            None,
-                        ))
-                    }
-                    Err(e) => return Err(ErrorObject::owned::<()>(-1, e.to_string(), None)),
-                };
-                (from, to)
-            }
-            (None, Some(from_block), Some(to_block)) => {
-                let from_tag = BlockNumberOrTag::from_str(&from_block)
-                    .map_err(|e| ErrorObject::owned::<()>(-1, e.to_string(), None))?;
-                let from = match provider.block_by_number_or_tag(from_tag) {
-                    Ok(Some(b)) => b.number,
-                    Ok(None) => {
-                        return Err(ErrorObject::owned::<()>(
-                            -1,
-                            format!("No block found for block number or tag: {from_tag}"),
-                            None,
-                        ))
-                    }
-                    Err(e) => return Err(ErrorObject::owned::<()>(-1, e.to_string(), None)),
-                };
-                let to_tag = BlockNumberOrTag::from_str(&to_block)
-                    .map_err(|e| ErrorObject::owned::<()>(-1, e.to_string(), None))?;
-                let to = match provider.block_by_number_or_tag(to_tag) {
-                    Ok(Some(b)) => b.number,
-                    Ok(None) => {
-                        return Err(ErrorObject::owned::<()>(
-                            -1,
-                            format!("No block found for block number or tag: {to_tag}"),
-                            None,
-                        ))
-                    }
-                    Err(e) => return Err(ErrorObject::owned::<()>(-1, e.to_string(), None)),
-                };
-
-                (from, to)
-            }
-            (Some(block_hash), None, None) => {
-                let num = match provider.block_by_hash(
-                    FixedBytes::from_str(&block_hash)
-                        .map_err(|e| ErrorObject::owned::<()>(-1, e.to_string(), None))?,
-                ) {
-                    Ok(Some(b)) => b.number,
-                    Ok(None) => {
-                        return Err(ErrorObject::owned::<()>(
-                            -1,
-                            format!("No block found for block hash: {block_hash}"),
-                            None,
-                        ))
-                    }
-                    Err(e) => return Err(ErrorObject::owned::<()>(-1, e.to_string(), None)),
-                };
-                (num, num)
-            }
-            (Some(_), Some(_), _) | (Some(_), _, Some(_)) => return Err(ErrorObject::owned::<()>(
-                -32001,
-                "Parameters fromBlock and toBlock cannot be used if blockHash parameter is present",
-                None,
-            )),
         };
 
-        if params.topics.len() > 4 {
-            return Err(ErrorObject::owned::<()>(32002, "Only up to four topics are allowed", None));
+        if from_block > to_block {
+            return Err(ShadowRpcError::InvertedBlockRange { from_block, to_block });
         }
 
-        let mut topics: [Option<String>; 4] = [None, None, None, None];
-
-        for (idx, topic) in params.topics.into_iter().enumerate() {
-            topics[idx] = Some(topic);
+        let latest = resolve_tag(provider, BlockNumberOrTag::Latest)?;
+        if to_block > latest {
+            return Err(ShadowRpcError::BlockRangeOutOfBounds { to_block, latest });
         }
 
-        Ok(ValidatedQueryParams { from_block, to_block, addresses: params.address, topics })
+        let addresses = filter.address.into_iter().collect();
+        let topics =
+            filter.topics.map(|set| if set.is_empty() { None } else { Some(set.into_iter().collect()) });
+
+        Ok(ValidatedQueryParams { from_block, to_block, addresses, topics })
     }
 }
 
-impl std::fmt::Display for ValidatedQueryParams {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let address_clause = if !self.addresses.is_empty() {
-            Some(format!(
-                "address IN ({})",
-                self.addresses
-                    .iter()
-                    .map(|addr| format!("X'{}'", &addr[2..]))
-                    .collect::<Vec<String>>()
-                    .join(", ")
-            ))
-        } else {
-            None
-        };
-
-        let block_range_clause =
-            Some(format!("block_number BETWEEN {} AND {}", self.from_block, self.to_block));
-
-        let topic_0_clause = self.topics[0].as_ref().map(|t0| format!("topic_0 = {t0}"));
-
-        let topic_1_clause = self.topics[1].as_ref().map(|t1| format!("topic_1 = {t1}"));
-
-        let topic_2_clause = self.topics[2].as_ref().map(|t2| format!("topic_2 = {t2}"));
-
-        let topic_3_clause = self.topics[3].as_ref().map(|t3| format!("topic_3 = {t3}"));
-
-        let clauses = [
-            address_clause,
-            block_range_clause,
-            topic_0_clause,
-            topic_1_clause,
-            topic_2_clause,
-            topic_3_clause,
-        ];
-
-        let filtered_clauses = clauses.into_iter().flatten().collect::<Vec<String>>();
-
-        if !filtered_clauses.is_empty() {
-            write!(f, "WHERE {}", filtered_clauses.join(" AND "))
-        } else {
-            write!(f, "")
-        }
+/// Resolves a [`BlockNumberOrTag`] to a concrete block number. An explicit number is returned
+/// as is, so that a number past the latest block is reported by the range bounds check.
+fn resolve_tag(
+    provider: &(impl BlockNumReader + BlockReaderIdExt + Clone + Unpin + 'static),
+    tag: BlockNumberOrTag,
+) -> Result<u64, ShadowRpcError> {
+    if let BlockNumberOrTag::Number(num) = tag {
+        return Ok(num);
     }
+    let block = provider
+        .block_by_number_or_tag(tag)?
+        .ok_or_else(|| ShadowRpcError::BlockNotFound(format!("block number or tag: {tag}")))?;
+    Ok(block.number)
 }
 
 #[cfg(test)]
 mod tests {
+    use alloy_primitives::{address, b256};
+    use alloy_rpc_types::Filter;
     use reth::providers::test_utils::MockEthProvider;
-    use reth_primitives::{Block, Header};
+    use reth_primitives::{Block, BlockNumberOrTag, Header};
 
-    use super::ValidatedQueryParams;
-
-    use super::GetLogsParameters;
+    use super::{GetLogsResult, RawGetLogsRow, ValidatedQueryParams};
+    use crate::error::ShadowRpcError;
 
     #[tokio::test]
     async fn test_query_param_validation() {
@@ -397,64 +328,105 @@ mod tests {
         mock_provider
             .extend_blocks([(first_block_hash, first_block), (last_block_hash, last_block)]);
 
-        let params_with_block_hash = GetLogsParameters {
-            address: vec!["0x123".to_string()],
-            block_hash: Some(last_block_hash.to_string()),
-            from_block: None,
-            to_block: None,
-            topics: vec![],
-        };
+        let address = address!("1234567890123456789012345678901234567890");
 
-        assert!(ValidatedQueryParams::new(&mock_provider, params_with_block_hash).is_ok());
+        let filter_with_block_hash = Filter::new().address(address).at_block_hash(last_block_hash);
+        assert!(ValidatedQueryParams::new(&mock_provider, filter_with_block_hash).is_ok());
 
-        let params_with_defaults = GetLogsParameters {
-            address: vec!["0x123".to_string()],
-            block_hash: None,
-            from_block: None,
-            to_block: None,
-            topics: vec![],
-        };
-
-        let validated = ValidatedQueryParams::new(&mock_provider, params_with_defaults);
+        let filter_with_defaults = Filter::new().address(address);
+        let validated = ValidatedQueryParams::new(&mock_provider, filter_with_defaults);
 
         assert_eq!(
             validated.unwrap(),
             ValidatedQueryParams {
-                addresses: vec!["0x123".to_string()],
+                addresses: vec![address],
                 from_block: 10,
                 to_block: 10,
                 topics: [None, None, None, None]
             }
         );
 
-        let params_with_block_tags = GetLogsParameters {
-            address: vec!["0x123".to_string()],
-            block_hash: None,
-            from_block: Some("earliest".to_string()),
-            to_block: Some("latest".to_string()),
-            topics: vec![],
-        };
-        let validated = ValidatedQueryParams::new(&mock_provider, params_with_block_tags);
+        let filter_with_block_tags = Filter::new()
+            .address(address)
+            .from_block(BlockNumberOrTag::Earliest)
+            .to_block(BlockNumberOrTag::Latest);
+        let validated = ValidatedQueryParams::new(&mock_provider, filter_with_block_tags);
 
         assert_eq!(
             validated.unwrap(),
             ValidatedQueryParams {
-                addresses: vec!["0x123".to_string()],
+                addresses: vec![address],
                 from_block: 0,
                 to_block: 10,
                 topics: [None, None, None, None]
             }
         );
 
-        let params_with_block_hash_and_range = GetLogsParameters {
-            address: vec!["0x123".to_string()],
-            block_hash: Some(first_block_hash.to_string()),
-            from_block: Some(first_block_hash.to_string()),
-            to_block: Some(last_block_hash.to_string()),
-            topics: vec![],
-        };
-        assert!(
-            ValidatedQueryParams::new(&mock_provider, params_with_block_hash_and_range).is_err()
-        );
+        let topic = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+        let other_topic = b256!("2222222222222222222222222222222222222222222222222222222222222222");
+        let filter_with_topic_or = Filter::new().address(address).topic0(vec![topic, other_topic]);
+        let validated = ValidatedQueryParams::new(&mock_provider, filter_with_topic_or).unwrap();
+        assert_eq!(validated.topics[0].as_ref().unwrap().len(), 2);
+        assert!(validated.topics[1].is_none());
+
+        let filter_with_inverted_range =
+            Filter::new().address(address).from_block(10u64).to_block(0u64);
+        assert!(matches!(
+            ValidatedQueryParams::new(&mock_provider, filter_with_inverted_range),
+            Err(ShadowRpcError::InvertedBlockRange { from_block: 10, to_block: 0 })
+        ));
+
+        let filter_past_latest = Filter::new().address(address).from_block(0u64).to_block(11u64);
+        assert!(matches!(
+            ValidatedQueryParams::new(&mock_provider, filter_past_latest),
+            Err(ShadowRpcError::BlockRangeOutOfBounds { to_block: 11, latest: 10 })
+        ));
+    }
+
+    fn raw_row(address: Vec<u8>, block_hash: Vec<u8>, transaction_hash: Vec<u8>) -> RawGetLogsRow {
+        RawGetLogsRow {
+            address,
+            block_hash,
+            block_log_index: 0,
+            block_number: 1,
+            data: vec![],
+            removed: false,
+            topic_0: None,
+            topic_1: None,
+            topic_2: None,
+            topic_3: None,
+            transaction_hash,
+            transaction_index: 0,
+            transaction_log_index: 0,
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn get_logs_result_conversion_rejects_malformed_columns_instead_of_panicking() {
+        let row = raw_row(vec![0u8; 20], vec![0u8; 32], vec![0u8; 32]);
+        assert!(GetLogsResult::try_from(row).is_ok());
+
+        let short_address = raw_row(vec![0u8; 19], vec![0u8; 32], vec![0u8; 32]);
+        assert!(matches!(
+            GetLogsResult::try_from(short_address),
+            Err(ShadowRpcError::MalformedRow { field: "address", len: 19 })
+        ));
+
+        let short_block_hash = raw_row(vec![0u8; 20], vec![0u8; 31], vec![0u8; 32]);
+        assert!(matches!(
+            GetLogsResult::try_from(short_block_hash),
+            Err(ShadowRpcError::MalformedRow { field: "block_hash", len: 31 })
+        ));
+    }
+
+    #[test]
+    fn get_logs_result_serializes_as_camel_case() {
+        let row = raw_row(vec![0u8; 20], vec![1u8; 32], vec![2u8; 32]);
+        let result = GetLogsResult::try_from(row).unwrap();
+
+        let json = serde_json::to_value(&result).unwrap();
+        for key in ["blockHash", "blockNumber", "logIndex", "transactionHash", "transactionIndex"] {
+            assert!(json.get(key).is_some(), "expected camelCase field `{key}` in {json}");
+        }
+    }
+}
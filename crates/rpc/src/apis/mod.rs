@@ -0,0 +1,43 @@
+//! RPC method implementations for the `shadow` namespace, split by endpoint group.
+
+pub(crate) mod filter;
+pub(crate) mod get_logs;
+pub(crate) mod rate_limit;
+
+use alloy_rpc_types::Filter;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    Extensions,
+};
+use reth::providers::{BlockNumReader, BlockReaderIdExt};
+
+use filter::FilterId;
+use get_logs::{GetLogsResponse, GetLogsResult, GetLogsRpcRequest};
+
+use crate::{ShadowRpc, ShadowRpcApiServer};
+
+#[async_trait]
+impl<P> ShadowRpcApiServer for ShadowRpc<P>
+where
+    P: BlockNumReader + BlockReaderIdExt + Clone + Unpin + 'static,
+{
+    async fn get_logs(&self, ext: &Extensions, req: GetLogsRpcRequest) -> RpcResult<GetLogsResponse> {
+        get_logs::get_logs(self, ext, req).await
+    }
+
+    async fn new_filter(&self, filter: Filter) -> RpcResult<FilterId> {
+        filter::new_filter(self, filter).await
+    }
+
+    async fn get_filter_changes(&self, ext: &Extensions, id: FilterId) -> RpcResult<Vec<GetLogsResult>> {
+        filter::get_filter_changes(self, ext, id).await
+    }
+
+    async fn get_filter_logs(&self, ext: &Extensions, id: FilterId) -> RpcResult<Vec<GetLogsResult>> {
+        filter::get_filter_logs(self, ext, id).await
+    }
+
+    async fn uninstall_filter(&self, id: FilterId) -> RpcResult<bool> {
+        filter::uninstall_filter(self, id).await
+    }
+}
@@ -0,0 +1,185 @@
+//! Credit-based cost accounting and rate limiting for `shadow_getLogs`, mirroring the
+//! compute-cost/deduct-cost flow control used by light clients: every query is priced, and each
+//! connection draws down a credit balance that recharges over wall-clock time.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+use jsonrpsee::{
+    core::RpcResult,
+    types::{error::CALL_EXECUTION_FAILED_CODE, ConnectionId, ErrorObject},
+    Extensions,
+};
+
+use super::get_logs::ValidatedQueryParams;
+
+/// Extracts the calling connection's id from `ext`, defaulting to `0` if unset (e.g. in tests
+/// that construct requests without going through the jsonrpsee server).
+pub(crate) fn connection_id(ext: &Extensions) -> u32 {
+    ext.get::<ConnectionId>().copied().unwrap_or_default().0
+}
+
+/// Base cost charged for every query, regardless of its range or filters.
+const BASE_COST: i64 = 10;
+/// Cost per block in the requested range.
+const RANGE_WEIGHT: i64 = 1;
+/// Cost per address filter.
+const ADDRESS_WEIGHT: i64 = 20;
+/// Cost per populated topic filter.
+const TOPIC_WEIGHT: i64 = 10;
+
+/// Default maximum credit balance for a connection's bucket.
+pub(crate) const DEFAULT_MAX_CREDITS: i64 = 1_000_000;
+/// Default credits recharged per second.
+pub(crate) const DEFAULT_RECHARGE_RATE: i64 = 50_000;
+
+/// Computes the cost of executing `params` against the shadow log database.
+pub(crate) fn compute_cost(params: &ValidatedQueryParams) -> i64 {
+    let range_cost = params.to_block.saturating_sub(params.from_block) as i64 * RANGE_WEIGHT;
+    let address_cost = params.addresses.len() as i64 * ADDRESS_WEIGHT;
+    let topic_cost = params.topics.iter().flatten().count() as i64 * TOPIC_WEIGHT;
+    BASE_COST + range_cost + address_cost + topic_cost
+}
+
+/// A recharging credit balance for a single connection.
+struct CreditBucket {
+    /// Credits currently available to spend.
+    credits: i64,
+    /// Last time this bucket was recharged.
+    last_recharge: Instant,
+    /// Wall-clock time of the bucket's last deduction, used by the reaper to expire idle buckets.
+    last_used: Instant,
+}
+
+impl CreditBucket {
+    fn new(max_credits: i64) -> Self {
+        let now = Instant::now();
+        Self { credits: max_credits, last_recharge: now, last_used: now }
+    }
+
+    fn recharge(&mut self, max_credits: i64, recharge_rate: i64) {
+        let elapsed = self.last_recharge.elapsed().as_secs_f64();
+        let recharged = (elapsed * recharge_rate as f64) as i64;
+        if recharged > 0 {
+            self.credits = (self.credits + recharged).min(max_credits);
+            self.last_recharge = Instant::now();
+        }
+    }
+}
+
+/// Per-connection credit buckets backing `shadow_getLogs` rate limiting.
+pub(crate) struct RateLimiter {
+    buckets: DashMap<u32, CreditBucket>,
+    max_credits: i64,
+    recharge_rate: i64,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(max_credits: i64, recharge_rate: i64) -> Self {
+        Self { buckets: DashMap::new(), max_credits, recharge_rate }
+    }
+
+    /// Deducts `cost` credits from `connection`'s bucket, rejecting the request instead of
+    /// running the query if the balance would go negative.
+    pub(crate) fn deduct(&self, connection: u32, cost: i64) -> RpcResult<()> {
+        let mut bucket =
+            self.buckets.entry(connection).or_insert_with(|| CreditBucket::new(self.max_credits));
+        bucket.recharge(self.max_credits, self.recharge_rate);
+        bucket.last_used = Instant::now();
+
+        if bucket.credits < cost {
+            return Err(ErrorObject::owned::<()>(
+                CALL_EXECUTION_FAILED_CODE,
+                format!(
+                    "rate limited: query costs {cost} credits but only {} are available",
+                    bucket.credits
+                ),
+                None,
+            ));
+        }
+
+        bucket.credits -= cost;
+        Ok(())
+    }
+
+    /// Drops buckets that haven't been used within `ttl`, so the bucket map doesn't grow
+    /// unbounded as connections come and go.
+    fn reap_idle(&self, ttl: Duration) {
+        self.buckets.retain(|_, bucket| bucket.last_used.elapsed() < ttl);
+    }
+}
+
+/// Periodically drops rate-limit buckets that haven't been used within `ttl`, mirroring
+/// [`super::filter::reap_idle_filters`].
+pub(crate) async fn reap_idle_buckets(limiter: Arc<RateLimiter>, ttl: Duration, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        limiter.reap_idle(ttl);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256};
+
+    use super::*;
+
+    fn params(from_block: u64, to_block: u64, addresses: usize, topics: usize) -> ValidatedQueryParams {
+        let topic = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+        let mut topic_sets = [None, None, None, None];
+        for slot in topic_sets.iter_mut().take(topics) {
+            *slot = Some(vec![topic]);
+        }
+
+        ValidatedQueryParams {
+            from_block,
+            to_block,
+            addresses: vec![address!("1234567890123456789012345678901234567890"); addresses],
+            topics: topic_sets,
+        }
+    }
+
+    #[test]
+    fn compute_cost_weighs_range_addresses_and_topics() {
+        assert_eq!(compute_cost(&params(0, 0, 0, 0)), BASE_COST);
+        assert_eq!(compute_cost(&params(0, 100, 0, 0)), BASE_COST + 100 * RANGE_WEIGHT);
+        assert_eq!(compute_cost(&params(0, 0, 1, 0)), BASE_COST + ADDRESS_WEIGHT);
+        assert_eq!(compute_cost(&params(0, 0, 0, 1)), BASE_COST + TOPIC_WEIGHT);
+    }
+
+    #[test]
+    fn deduct_rejects_when_under_cost_and_spends_credits_on_success() {
+        let limiter = RateLimiter::new(100, 0);
+        assert!(limiter.deduct(1, 50).is_ok());
+        assert!(limiter.deduct(1, 60).is_err(), "only 50 credits remain");
+        assert!(limiter.deduct(1, 50).is_ok());
+    }
+
+    #[test]
+    fn recharge_accrues_over_elapsed_time_and_clamps_at_max_credits() {
+        let mut bucket = CreditBucket::new(100);
+        bucket.credits = 0;
+        bucket.last_recharge = Instant::now() - Duration::from_secs(1);
+
+        bucket.recharge(100, 50);
+        assert_eq!(bucket.credits, 50);
+
+        bucket.last_recharge = Instant::now() - Duration::from_secs(10);
+        bucket.recharge(100, 50);
+        assert_eq!(bucket.credits, 100, "recharge must clamp at max_credits");
+    }
+
+    #[test]
+    fn reap_idle_drops_buckets_untouched_past_ttl() {
+        let limiter = RateLimiter::new(100, 0);
+        limiter.deduct(1, 1).unwrap();
+        limiter.buckets.get_mut(&1).unwrap().last_used = Instant::now() - Duration::from_secs(120);
+
+        limiter.reap_idle(Duration::from_secs(60));
+        assert!(limiter.buckets.is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+//! Typed errors for the `shadow` RPC namespace, mapped to standard Ethereum JSON-RPC error codes.
+
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject, ErrorObjectOwned,
+};
+use reth::providers::ProviderError;
+
+/// Errors produced while validating or executing `shadow` RPC requests.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ShadowRpcError {
+    /// `fromBlock` is greater than `toBlock`.
+    #[error("fromBlock ({from_block}) is greater than toBlock ({to_block})")]
+    InvertedBlockRange {
+        /// Start of the requested block range.
+        from_block: u64,
+        /// End of the requested block range.
+        to_block: u64,
+    },
+
+    /// The requested range extends past the latest indexed block.
+    #[error("toBlock ({to_block}) exceeds the latest indexed block ({latest})")]
+    BlockRangeOutOfBounds {
+        /// End of the requested block range.
+        to_block: u64,
+        /// The latest block known to the node.
+        latest: u64,
+    },
+
+    /// A block tag or hash could not be resolved to a known block.
+    #[error("no block found for {0}")]
+    BlockNotFound(String),
+
+    /// The underlying provider returned an error while resolving a block.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+
+    /// A `shadow_logs` row had a fixed-width column (address, hash, ...) of the wrong length,
+    /// e.g. from a schema change or a buggy indexer write.
+    #[error("malformed shadow_logs row: {field} has {len} bytes")]
+    MalformedRow {
+        /// Name of the column that failed to decode.
+        field: &'static str,
+        /// The column's actual byte length.
+        len: usize,
+    },
+}
+
+impl From<ShadowRpcError> for ErrorObjectOwned {
+    fn from(err: ShadowRpcError) -> Self {
+        let code = match err {
+            ShadowRpcError::Provider(_) | ShadowRpcError::MalformedRow { .. } => {
+                INTERNAL_ERROR_CODE
+            }
+            ShadowRpcError::InvertedBlockRange { .. }
+            | ShadowRpcError::BlockRangeOutOfBounds { .. }
+            | ShadowRpcError::BlockNotFound(_) => INVALID_PARAMS_CODE,
+        };
+        ErrorObject::owned::<()>(code, err.to_string(), None)
+    }
+}
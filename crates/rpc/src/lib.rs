@@ -0,0 +1,107 @@
+//! Shadow Reth RPC: exposes the `shadow` JSON-RPC namespace for querying shadow log data
+//! recorded by [`shadow-reth-exex`].
+
+mod apis;
+mod error;
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use alloy_rpc_types::Filter;
+use dashmap::DashMap;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, Extensions};
+use reth::providers::{BlockNumReader, BlockReaderIdExt};
+use reth_node_api::FullNodeComponents;
+use reth_node_builder::rpc::RpcContext;
+use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+
+use apis::{
+    filter::FilterId,
+    get_logs::{GetLogsResponse, GetLogsResult, GetLogsRpcRequest},
+    rate_limit::{RateLimiter, DEFAULT_MAX_CREDITS, DEFAULT_RECHARGE_RATE},
+};
+
+/// How long a filter may sit unpolled before the reaper drops it.
+const DEFAULT_FILTER_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// How often the reaper sweeps for idle filters.
+const FILTER_REAP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a connection's rate-limit bucket may sit unused before the reaper drops it.
+const DEFAULT_BUCKET_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// How often the reaper sweeps for idle rate-limit buckets.
+const BUCKET_REAP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `shadow` JSON-RPC namespace.
+#[rpc(server, namespace = "shadow")]
+pub trait ShadowRpcApi {
+    /// Returns shadow logs matching the given filter parameters. Costs credits from the calling
+    /// connection's rate-limit bucket, proportional to the size of the query.
+    #[method(name = "getLogs")]
+    async fn get_logs(&self, ext: &Extensions, req: GetLogsRpcRequest) -> RpcResult<GetLogsResponse>;
+
+    /// Creates a new shadow log filter and returns its id.
+    #[method(name = "newFilter")]
+    async fn new_filter(&self, filter: Filter) -> RpcResult<FilterId>;
+
+    /// Returns the shadow logs added since the filter was last polled. Costs credits from the
+    /// calling connection's rate-limit bucket, proportional to the size of the underlying query.
+    #[method(name = "getFilterChanges")]
+    async fn get_filter_changes(&self, ext: &Extensions, id: FilterId) -> RpcResult<Vec<GetLogsResult>>;
+
+    /// Returns all shadow logs matching a filter's parameters. Costs credits from the calling
+    /// connection's rate-limit bucket, proportional to the size of the underlying query.
+    #[method(name = "getFilterLogs")]
+    async fn get_filter_logs(&self, ext: &Extensions, id: FilterId) -> RpcResult<Vec<GetLogsResult>>;
+
+    /// Uninstalls a filter, returning whether it existed.
+    #[method(name = "uninstallFilter")]
+    async fn uninstall_filter(&self, id: FilterId) -> RpcResult<bool>;
+}
+
+/// RPC handler for the `shadow` namespace, backed by the SQLite database populated by
+/// [`shadow-reth-exex`].
+#[derive(Clone)]
+pub struct ShadowRpc<P> {
+    /// Handle to the SQLite pool backing shadow log storage.
+    pub(crate) pool: SqlitePool,
+    /// Handle to the node's blockchain provider, used to resolve block tags and hashes.
+    pub(crate) provider: P,
+    /// Live poll filters, keyed by their opaque id.
+    pub(crate) filters: Arc<DashMap<FilterId, apis::filter::PollFilter>>,
+    /// Per-connection credit buckets used to rate limit `shadow_getLogs`.
+    pub(crate) rate_limiter: Arc<RateLimiter>,
+}
+
+impl<P> ShadowRpc<P>
+where
+    P: BlockNumReader + BlockReaderIdExt + Clone + Unpin + 'static,
+{
+    /// Initializes the shadow RPC module, registers it on `ctx`, and spawns the filter reaper.
+    pub fn init<Node: FullNodeComponents>(
+        ctx: RpcContext<'_, Node>,
+        db_path: PathBuf,
+        provider: P,
+    ) -> eyre::Result<()> {
+        let pool =
+            SqlitePoolOptions::new().connect_lazy(&format!("sqlite://{}", db_path.display()))?;
+        let filters = Arc::new(DashMap::new());
+        let rate_limiter = Arc::new(RateLimiter::new(DEFAULT_MAX_CREDITS, DEFAULT_RECHARGE_RATE));
+
+        tokio::spawn(apis::filter::reap_idle_filters(
+            filters.clone(),
+            DEFAULT_FILTER_TTL,
+            FILTER_REAP_INTERVAL,
+        ));
+        tokio::spawn(apis::rate_limit::reap_idle_buckets(
+            rate_limiter.clone(),
+            DEFAULT_BUCKET_TTL,
+            BUCKET_REAP_INTERVAL,
+        ));
+
+        let rpc = Self { pool, provider, filters, rate_limiter };
+        ctx.modules.merge_configured(rpc.into_rpc())?;
+
+        Ok(())
+    }
+}